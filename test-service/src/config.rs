@@ -0,0 +1,85 @@
+use std::env;
+
+/// Server settings resolved from the environment, with defaults sane enough to
+/// run locally untouched.
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub workers: usize,
+    pub tls_cert: Option<String>,
+    pub tls_key: Option<String>,
+    /// How long an idle keep-alive connection is held open, in seconds.
+    pub keep_alive_secs: u64,
+    /// How long a client has to send a complete request before it's dropped, in seconds.
+    pub client_request_timeout_secs: u64,
+    /// How long to wait for a client to close the connection after a disconnect, in seconds.
+    pub client_disconnect_timeout_secs: u64,
+    /// Whether the `Logger` middleware is wrapped into the app.
+    pub enable_logger: bool,
+    /// Whether the `Compress` middleware is wrapped into the app.
+    pub enable_compress: bool,
+    /// Whether the `DefaultHeaders` middleware (and its `X-Version` header) is wrapped into the app.
+    pub enable_default_headers: bool,
+    /// Value sent back in the `X-Version` response header when `enable_default_headers` is set.
+    pub app_version: String,
+}
+
+fn env_bool(key: &str, default: bool) -> bool {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        let host = env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let port = env::var("PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(8080);
+        let workers = env::var("WORKERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(num_cpus::get);
+        let tls_cert = env::var("TLS_CERT").ok();
+        let tls_key = env::var("TLS_KEY").ok();
+        let keep_alive_secs = env::var("KEEP_ALIVE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let client_request_timeout_secs = env::var("CLIENT_REQUEST_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let client_disconnect_timeout_secs = env::var("CLIENT_DISCONNECT_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let enable_logger = env_bool("ENABLE_LOGGER", true);
+        let enable_compress = env_bool("ENABLE_COMPRESS", true);
+        let enable_default_headers = env_bool("ENABLE_DEFAULT_HEADERS", true);
+        let app_version =
+            env::var("APP_VERSION").unwrap_or_else(|_| env!("CARGO_PKG_VERSION").to_string());
+
+        Self {
+            host,
+            port,
+            workers,
+            tls_cert,
+            tls_key,
+            keep_alive_secs,
+            client_request_timeout_secs,
+            client_disconnect_timeout_secs,
+            enable_logger,
+            enable_compress,
+            enable_default_headers,
+            app_version,
+        }
+    }
+
+    /// Whether both halves of a TLS cert/key pair were supplied.
+    pub fn tls_enabled(&self) -> bool {
+        self.tls_cert.is_some() && self.tls_key.is_some()
+    }
+}
@@ -1,12 +1,247 @@
-use actix_web::{web, App, HttpResponse, HttpServer};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use actix_session::storage::CookieSessionStore;
+use actix_session::{Session, SessionMiddleware};
+use actix_web::cookie::Key;
+use actix_web::middleware::{Compress, Condition, DefaultHeaders, Logger};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use serde::Serialize;
+
+mod config;
+mod tls;
+
+use config::Config;
+
+/// Consistent JSON shape for every non-2xx response the scaffold returns.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(serde::Deserialize))]
+struct ErrorBody {
+    error: String,
+}
+
+impl ErrorBody {
+    fn new(message: impl Into<String>) -> Self {
+        Self { error: message.into() }
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+type Users = HashMap<u32, User>;
+
+fn seed_users() -> Users {
+    let mut users = HashMap::new();
+    users.insert(1, User { id: 1, name: "Ada Lovelace".into() });
+    users.insert(2, User { id: 2, name: "Grace Hopper".into() });
+    users
+}
+
+/// `web::Path` extraction failures (e.g. a non-numeric `user_id`) happen after the
+/// route has matched, so they never reach `default_service` and otherwise return
+/// actix's bare plain-text 404 — wrap them in the same JSON error envelope as the
+/// rest of the scaffold.
+fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|err, _req| {
+        let response = HttpResponse::NotFound().json(ErrorBody::new("not found"));
+        actix_web::error::InternalError::from_response(err, response).into()
+    })
+}
+
+async fn get_user(path: web::Path<u32>, users: web::Data<Users>) -> HttpResponse {
+    let user_id = path.into_inner();
+    match users.get(&user_id) {
+        Some(user) => HttpResponse::Ok().json(user),
+        None => HttpResponse::NotFound().json(ErrorBody::new("user not found")),
+    }
+}
+
+/// Wraps a session read/write failure in the JSON error envelope as a 500.
+fn session_error<E: std::fmt::Debug + std::fmt::Display + 'static>(err: E) -> actix_web::Error {
+    let response = HttpResponse::InternalServerError().json(ErrorBody::new("session error"));
+    actix_web::error::InternalError::from_response(err, response).into()
+}
+
+async fn visit_count(session: Session) -> actix_web::Result<HttpResponse> {
+    let count: u32 = session.get("counter").map_err(session_error)?.unwrap_or(0) + 1;
+    session.insert("counter", count).map_err(session_error)?;
+    Ok(HttpResponse::Ok().json(count))
+}
+
+/// Whether `path` matches one of the app's declared routes, regardless of method.
+/// Kept in sync with the `.route(...)` calls registered on `App` in `main`.
+fn is_known_route(path: &str) -> bool {
+    let segments: Vec<&str> = path.strip_prefix('/').unwrap_or(path).split('/').collect();
+    match segments.as_slice() {
+        [""] => true,
+        ["users", user_id] => user_id.parse::<u32>().is_ok(),
+        ["visits"] => true,
+        _ => false,
+    }
+}
+
+/// Handles any request that doesn't match a declared route: hitting a known path
+/// with the wrong method is a 405, anything else is an unknown path (404). Since
+/// `default_service` only runs once routing has already failed to match, a known
+/// path landing here always means the method was wrong.
+async fn not_found_or_method_not_allowed(req: HttpRequest) -> HttpResponse {
+    if is_known_route(req.path()) {
+        HttpResponse::MethodNotAllowed().json(ErrorBody::new("method not allowed"))
+    } else {
+        HttpResponse::NotFound().json(ErrorBody::new("not found"))
+    }
+}
+
+/// Reads the 64-byte session signing key from `SESSION_KEY` (hex-encoded) so the
+/// cookie store stays valid across worker threads and restarts, falling back to a
+/// freshly generated key in dev where persistence doesn't matter.
+fn session_key() -> Key {
+    match std::env::var("SESSION_KEY") {
+        Ok(hex_key) => {
+            let bytes = hex::decode(hex_key).expect("SESSION_KEY must be valid hex");
+            Key::from(&bytes)
+        }
+        Err(_) => Key::generate(),
+    }
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    HttpServer::new(|| {
+    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let users = web::Data::new(seed_users());
+    let signing_key = session_key();
+    let config = Config::from_env();
+    let cookie_secure = config.tls_enabled();
+    let enable_logger = config.enable_logger;
+    let enable_compress = config.enable_compress;
+    let enable_default_headers = config.enable_default_headers;
+    let app_version = config.app_version.clone();
+
+    println!("starting on {}:{} with {} worker(s)", config.host, config.port, config.workers);
+
+    let server = HttpServer::new(move || {
         App::new()
+            .wrap(Condition::new(enable_logger, Logger::default()))
+            .wrap(Condition::new(enable_compress, Compress::default()))
+            .wrap(Condition::new(
+                enable_default_headers,
+                DefaultHeaders::new().add(("X-Version", app_version.clone())),
+            ))
+            .wrap(
+                SessionMiddleware::builder(CookieSessionStore::default(), signing_key.clone())
+                    .cookie_secure(cookie_secure)
+                    .build(),
+            )
+            .app_data(users.clone())
+            .app_data(path_config())
             .route("/", web::get().to(|| async { HttpResponse::Ok().json("Hello World") }))
+            .route("/users/{user_id}", web::get().to(get_user))
+            .route("/visits", web::get().to(visit_count))
+            .default_service(web::route().to(not_found_or_method_not_allowed))
     })
-    .bind("127.0.0.1:8080")?
-    .run()
-    .await
+    .workers(config.workers)
+    .keep_alive(Duration::from_secs(config.keep_alive_secs))
+    .client_request_timeout(Duration::from_secs(config.client_request_timeout_secs))
+    .client_disconnect_timeout(Duration::from_secs(config.client_disconnect_timeout_secs));
+
+    let server = if let (Some(cert), Some(key)) = (&config.tls_cert, &config.tls_key) {
+        println!("TLS enabled, terminating HTTPS at {}:{}", config.host, config.port);
+        rustls::crypto::ring::default_provider()
+            .install_default()
+            .expect("failed to install rustls CryptoProvider");
+        server.bind_rustls_0_23((config.host.as_str(), config.port), tls::load_rustls_config(cert, key))?
+    } else {
+        server.bind((config.host.as_str(), config.port))?
+    };
+
+    server.run().await
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test as actix_test;
+
+    use super::*;
+
+    #[test]
+    fn known_route_table() {
+        assert!(is_known_route("/"));
+        assert!(is_known_route("/users/5"));
+        assert!(!is_known_route("/users/abc"));
+        assert!(is_known_route("/visits"));
+        assert!(!is_known_route("/nope"));
+    }
+
+    #[actix_web::test]
+    async fn malformed_user_id_returns_json_404() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(seed_users()))
+                .app_data(path_config())
+                .route("/users/{user_id}", web::get().to(get_user))
+                .default_service(web::route().to(not_found_or_method_not_allowed)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::get().uri("/users/abc").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: ErrorBody = actix_test::read_body_json(resp).await;
+        assert_eq!(body.error, "not found");
+    }
+
+    #[actix_web::test]
+    async fn unknown_path_is_404_known_path_wrong_method_is_405() {
+        let app = actix_test::init_service(
+            App::new()
+                .route("/visits", web::get().to(visit_count))
+                .default_service(web::route().to(not_found_or_method_not_allowed)),
+        )
+        .await;
+
+        let wrong_method = actix_test::TestRequest::post().uri("/visits").to_request();
+        let resp = actix_test::call_service(&app, wrong_method).await;
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let unknown_path = actix_test::TestRequest::get().uri("/nope").to_request();
+        let resp = actix_test::call_service(&app, unknown_path).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn visit_count_increments_across_requests_with_the_same_session() {
+        let signing_key = Key::generate();
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(
+                    SessionMiddleware::builder(CookieSessionStore::default(), signing_key)
+                        .cookie_secure(false)
+                        .build(),
+                )
+                .route("/visits", web::get().to(visit_count)),
+        )
+        .await;
+
+        let first = actix_test::TestRequest::get().uri("/visits").to_request();
+        let resp = actix_test::call_service(&app, first).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let session_cookie = resp.response().cookies().next().expect("session cookie set").into_owned();
+        let count: u32 = actix_test::read_body_json(resp).await;
+        assert_eq!(count, 1);
+
+        let second = actix_test::TestRequest::get()
+            .uri("/visits")
+            .cookie(session_cookie)
+            .to_request();
+        let resp = actix_test::call_service(&app, second).await;
+        let count: u32 = actix_test::read_body_json(resp).await;
+        assert_eq!(count, 2);
+    }
 }
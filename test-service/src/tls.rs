@@ -0,0 +1,43 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::ServerConfig;
+
+/// Builds a rustls `ServerConfig` from a PEM certificate chain and private key so
+/// the server can terminate HTTPS directly via `bind_rustls_0_23`. Requires a
+/// process-level `CryptoProvider` to already be installed (see `main`).
+pub fn load_rustls_config(cert_path: &str, key_path: &str) -> ServerConfig {
+    let cert_file = &mut BufReader::new(File::open(cert_path).expect("failed to open TLS_CERT"));
+    let key_file = &mut BufReader::new(File::open(key_path).expect("failed to open TLS_KEY"));
+
+    let cert_chain: Vec<CertificateDer> = rustls_pemfile::certs(cert_file)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("failed to parse TLS_CERT");
+
+    let key: PrivateKeyDer = rustls_pemfile::private_key(key_file)
+        .expect("failed to parse TLS_KEY")
+        .expect("no private key found in TLS_KEY");
+
+    ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, key)
+        .expect("invalid TLS certificate/key pair")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_a_valid_cert_and_key_pair() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let cert_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/localhost.crt");
+        let key_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/localhost.key");
+
+        let config = load_rustls_config(cert_path, key_path);
+
+        assert!(config.alpn_protocols.is_empty());
+    }
+}